@@ -0,0 +1,241 @@
+use crate::handler::ConnectionHandler;
+use libp2p_core::connection::{ConnectionId, ListenerId};
+use libp2p_core::{Endpoint, Multiaddr, PeerId};
+use std::error::Error;
+use std::task::{Context, Poll};
+use std::{fmt, fmt::Debug};
+
+/// The reason why a pending or established connection was denied by a [`NetworkBehaviour`].
+///
+/// Wraps an opaque, boxed [`Error`] so that individual behaviours can surface their own
+/// domain-specific denial reason (e.g. "peer is banned") without this type needing to know
+/// about it.
+pub struct ConnectionDenied {
+    inner: Box<dyn Error + Send + Sync + 'static>,
+}
+
+impl ConnectionDenied {
+    pub fn new(cause: impl Into<Box<dyn Error + Send + Sync + 'static>>) -> Self {
+        Self {
+            inner: cause.into(),
+        }
+    }
+}
+
+impl fmt::Debug for ConnectionDenied {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Debug::fmt(&self.inner, f)
+    }
+}
+
+impl fmt::Display for ConnectionDenied {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.inner, f)
+    }
+}
+
+impl Error for ConnectionDenied {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(self.inner.as_ref())
+    }
+}
+
+/// A behaviour for the network. Allows customizing the swarm's behaviour for each connection
+/// and every inbound or outbound substream.
+pub trait NetworkBehaviour {
+    /// Handler for all the protocols the network behaviour supports.
+    type ConnectionHandler: ConnectionHandler;
+
+    /// Event generated by the `NetworkBehaviour` and that the swarm will report back.
+    type OutEvent: Send + 'static;
+
+    /// Callback that is invoked for every inbound connection, before a [`ConnectionHandler`] is
+    /// created for it.
+    ///
+    /// Denying a connection here means the [`Swarm`](crate::Swarm) never constructs a handler
+    /// for it and surfaces the reason as a [`FromSwarm::ListenFailure`].
+    fn handle_pending_inbound_connection(
+        &mut self,
+        _connection_id: ConnectionId,
+        _local_addr: &Multiaddr,
+        _remote_addr: &Multiaddr,
+    ) -> Result<(), ConnectionDenied> {
+        Ok(())
+    }
+
+    /// Callback that is invoked once an inbound connection has been fully established, i.e. the
+    /// remote [`PeerId`] is known.
+    ///
+    /// Returning `Err` denies the connection: no handler is constructed and the reason is
+    /// surfaced as a [`FromSwarm::ListenFailure`].
+    fn handle_established_inbound_connection(
+        &mut self,
+        connection_id: ConnectionId,
+        peer: PeerId,
+        local_addr: &Multiaddr,
+        remote_addr: &Multiaddr,
+    ) -> Result<Self::ConnectionHandler, ConnectionDenied>;
+
+    /// Callback that is invoked for every outbound connection attempt, before a
+    /// [`ConnectionHandler`] is created for it.
+    ///
+    /// The returned addresses are tried in addition to the ones already known for the peer.
+    /// Denying a connection here surfaces the reason as a [`FromSwarm::DialFailure`].
+    fn handle_pending_outbound_connection(
+        &mut self,
+        _connection_id: ConnectionId,
+        _maybe_peer: Option<PeerId>,
+        _addresses: &[Multiaddr],
+        _effective_role: Endpoint,
+    ) -> Result<Vec<Multiaddr>, ConnectionDenied> {
+        Ok(Vec::new())
+    }
+
+    /// Callback that is invoked once an outbound connection has been fully established.
+    ///
+    /// Returning `Err` denies the connection: no handler is constructed and the reason is
+    /// surfaced as a [`FromSwarm::DialFailure`].
+    fn handle_established_outbound_connection(
+        &mut self,
+        connection_id: ConnectionId,
+        peer: PeerId,
+        addr: &Multiaddr,
+        role_override: Endpoint,
+    ) -> Result<Self::ConnectionHandler, ConnectionDenied>;
+
+    /// Informs the behaviour about an event generated by the [`ConnectionHandler`] dedicated to
+    /// the connection identified by `peer_id` and `connection_id`.
+    fn on_connection_handler_event(
+        &mut self,
+        peer_id: PeerId,
+        connection_id: ConnectionId,
+        event: <Self::ConnectionHandler as ConnectionHandler>::OutEvent,
+    );
+
+    /// Polls for things that swarm should do, such as generate events or dial new peers.
+    fn poll(
+        &mut self,
+        cx: &mut Context<'_>,
+        params: &mut impl PollParameters,
+    ) -> Poll<NetworkBehaviourAction<Self::OutEvent, Self::ConnectionHandler>>;
+
+    /// Informs the behaviour about an event from the [`Swarm`](crate::Swarm).
+    fn on_swarm_event(&mut self, event: FromSwarm<Self::ConnectionHandler>);
+}
+
+/// Enumerates the different events that a [`NetworkBehaviour`] can receive from the
+/// [`Swarm`](crate::Swarm) via [`NetworkBehaviour::on_swarm_event`].
+pub enum FromSwarm<THandler: ConnectionHandler> {
+    ConnectionEstablished(ConnectionEstablished),
+    ConnectionClosed(ConnectionClosed<THandler>),
+    AddressChange(AddressChange),
+    DialFailure(DialFailure),
+    ListenFailure(ListenFailure),
+    NewListener(NewListener),
+    NewListenAddr(NewListenAddr),
+    ExpiredListenAddr(ExpiredListenAddr),
+    ListenerError(ListenerError),
+    ListenerClosed(ListenerClosed),
+    NewExternalAddr(NewExternalAddr),
+    ExpiredExternalAddr(ExpiredExternalAddr),
+}
+
+pub struct ConnectionEstablished {
+    pub peer_id: PeerId,
+    pub connection_id: ConnectionId,
+    pub endpoint: Endpoint,
+}
+
+pub struct ConnectionClosed<THandler: ConnectionHandler> {
+    pub peer_id: PeerId,
+    pub connection_id: ConnectionId,
+    pub handler: THandler,
+}
+
+pub struct AddressChange {
+    pub peer_id: PeerId,
+    pub connection_id: ConnectionId,
+    pub new_endpoint: Endpoint,
+}
+
+pub struct DialFailure {
+    pub peer_id: Option<PeerId>,
+    pub connection_id: ConnectionId,
+    pub error: ConnectionDenied,
+}
+
+pub struct ListenFailure {
+    pub local_addr: Multiaddr,
+    pub send_back_addr: Multiaddr,
+    pub connection_id: ConnectionId,
+    pub error: ConnectionDenied,
+}
+
+pub struct NewListener {
+    pub listener_id: ListenerId,
+}
+
+pub struct NewListenAddr {
+    pub listener_id: ListenerId,
+    pub addr: Multiaddr,
+}
+
+pub struct ExpiredListenAddr {
+    pub listener_id: ListenerId,
+    pub addr: Multiaddr,
+}
+
+pub struct ListenerError {
+    pub listener_id: ListenerId,
+}
+
+pub struct ListenerClosed {
+    pub listener_id: ListenerId,
+}
+
+pub struct NewExternalAddr {
+    pub addr: Multiaddr,
+}
+
+pub struct ExpiredExternalAddr {
+    pub addr: Multiaddr,
+}
+
+/// An action that a [`NetworkBehaviour`] can trigger in the [`Swarm`](crate::Swarm) as a result
+/// of being polled.
+pub enum NetworkBehaviourAction<TOutEvent, THandler: ConnectionHandler> {
+    /// Instructs the `Swarm` to return an event when it is being polled.
+    GenerateEvent(TOutEvent),
+    /// Instructs the `Swarm` to dial the given peer or addresses.
+    Dial { opts: DialOpts },
+    /// Instructs the `Swarm` to send an event to the [`ConnectionHandler`] of a connection.
+    NotifyHandler {
+        peer_id: PeerId,
+        connection_id: ConnectionId,
+        event: THandler::InEvent,
+    },
+    /// Instructs the `Swarm` to close a connection.
+    CloseConnection {
+        peer_id: PeerId,
+        connection_id: ConnectionId,
+    },
+}
+
+/// Options for a dial attempt issued via [`NetworkBehaviourAction::Dial`].
+pub struct DialOpts {
+    pub peer_id: Option<PeerId>,
+    pub addresses: Vec<Multiaddr>,
+}
+
+/// Parameters passed to [`NetworkBehaviour::poll`], giving a behaviour read access to the
+/// `Swarm`'s current listening and external addresses.
+pub trait PollParameters {
+    type ListenedAddressesIter: Iterator<Item = Multiaddr>;
+    type ExternalAddressesIter: Iterator<Item = Multiaddr>;
+
+    /// Returns the list of addresses the local node is listening on.
+    fn listened_addresses(&self) -> Self::ListenedAddressesIter;
+
+    /// Returns the list of external addresses known to the local node.
+    fn external_addresses(&self) -> Self::ExternalAddressesIter;
+}
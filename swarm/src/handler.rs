@@ -0,0 +1,215 @@
+use libp2p_core::muxing::SubstreamBox;
+use libp2p_core::upgrade::{InboundUpgrade, OutboundUpgrade};
+use std::task::{Context, Poll};
+use std::time::Instant;
+
+mod map_in;
+mod map_out;
+
+pub use map_in::MapInEvent;
+pub use map_out::MapOutEvent;
+
+/// The amount of time that a connection should be kept alive by a [`ConnectionHandler`].
+pub enum KeepAlive {
+    /// Keep the connection alive until the given [`Instant`].
+    Until(Instant),
+    /// Close the connection as soon as possible.
+    No,
+    /// Keep the connection alive forever.
+    Yes,
+}
+
+/// A prototype for a protocol upgrade, combined with the data that should be passed back once
+/// the upgrade has completed.
+pub struct SubstreamProtocol<TUpgrade, TInfo> {
+    upgrade: TUpgrade,
+    info: TInfo,
+}
+
+impl<TUpgrade, TInfo> SubstreamProtocol<TUpgrade, TInfo> {
+    pub fn new(upgrade: TUpgrade, info: TInfo) -> Self {
+        Self { upgrade, info }
+    }
+
+    pub fn upgrade(&self) -> &TUpgrade {
+        &self.upgrade
+    }
+
+    pub fn info(&self) -> &TInfo {
+        &self.info
+    }
+}
+
+/// Marker trait for inbound upgrades that can be sent across threads, as required by
+/// [`ConnectionHandler::InboundProtocol`].
+pub trait InboundUpgradeSend: Send + 'static {
+    type Output: Send + 'static;
+    type Error: Send + 'static;
+}
+
+impl<T> InboundUpgradeSend for T
+where
+    T: InboundUpgrade<SubstreamBox> + Send + 'static,
+    T::Output: Send + 'static,
+    T::Error: Send + 'static,
+    T::Future: Send + 'static,
+{
+    type Output = T::Output;
+    type Error = T::Error;
+}
+
+/// Marker trait for outbound upgrades that can be sent across threads, as required by
+/// [`ConnectionHandler::OutboundProtocol`].
+pub trait OutboundUpgradeSend: Send + 'static {
+    type Output: Send + 'static;
+    type Error: Send + 'static;
+}
+
+impl<T> OutboundUpgradeSend for T
+where
+    T: OutboundUpgrade<SubstreamBox> + Send + 'static,
+    T::Output: Send + 'static,
+    T::Error: Send + 'static,
+    T::Future: Send + 'static,
+{
+    type Output = T::Output;
+    type Error = T::Error;
+}
+
+/// Event produced by a [`ConnectionHandler`] once a substream it requested has been fully
+/// negotiated on the inbound side.
+pub struct FullyNegotiatedInbound<IP: InboundUpgradeSend, IOI> {
+    pub protocol: IP::Output,
+    pub info: IOI,
+}
+
+/// Event produced by a [`ConnectionHandler`] once a substream it requested has been fully
+/// negotiated on the outbound side.
+pub struct FullyNegotiatedOutbound<OP: OutboundUpgradeSend, OOI> {
+    pub protocol: OP::Output,
+    pub info: OOI,
+}
+
+/// A requested outbound substream failed to be negotiated.
+pub struct DialUpgradeError<OP: OutboundUpgradeSend, OOI> {
+    pub info: OOI,
+    pub error: OP::Error,
+}
+
+/// An inbound substream failed to be negotiated.
+pub struct ListenUpgradeError<IP: InboundUpgradeSend, IOI> {
+    pub info: IOI,
+    pub error: IP::Error,
+}
+
+/// The local or remote address of a connection has changed.
+pub struct AddressChange {
+    pub new_address: libp2p_core::Multiaddr,
+}
+
+/// Event produced by a [`crate::Swarm`] and passed to a [`ConnectionHandler`] via
+/// [`ConnectionHandler::on_connection_event`].
+pub enum ConnectionEvent<IP: InboundUpgradeSend, OP: OutboundUpgradeSend, IOI, OOI> {
+    FullyNegotiatedInbound(FullyNegotiatedInbound<IP, IOI>),
+    FullyNegotiatedOutbound(FullyNegotiatedOutbound<OP, OOI>),
+    DialUpgradeError(DialUpgradeError<OP, OOI>),
+    ListenUpgradeError(ListenUpgradeError<IP, IOI>),
+    AddressChange(AddressChange),
+}
+
+/// Event produced by a [`ConnectionHandler`] and returned from [`ConnectionHandler::poll`].
+pub enum ConnectionHandlerEvent<TOutboundProtocol, TOutboundOpenInfo, TCustom> {
+    /// Request a new outbound substream to be opened with the remote.
+    OutboundSubstreamRequest {
+        protocol: SubstreamProtocol<TOutboundProtocol, TOutboundOpenInfo>,
+    },
+    /// Other event to be reported to the [`NetworkBehaviour`](crate::NetworkBehaviour).
+    Custom(TCustom),
+}
+
+/// The trait that each connection's protocol handler has to implement.
+///
+/// Once a connection to a remote peer is established, a [`ConnectionHandler`] negotiates and
+/// handles one or more specific protocols on top of it.
+pub trait ConnectionHandler: Send + 'static {
+    /// Custom event that can be received from the [`NetworkBehaviour`](crate::NetworkBehaviour).
+    type InEvent: Send + 'static;
+    /// Custom event that can be produced by this handler and that will be returned to the
+    /// [`NetworkBehaviour`](crate::NetworkBehaviour).
+    type OutEvent: Send + 'static;
+    /// The inbound upgrade for the protocol(s) used by the handler.
+    type InboundProtocol: InboundUpgradeSend;
+    /// The outbound upgrade for the protocol(s) used by the handler.
+    type OutboundProtocol: OutboundUpgradeSend;
+    /// Information used by the handler to identify an inbound substream once negotiated.
+    type InboundOpenInfo: Send + 'static;
+    /// Information used by the handler to identify an outbound substream once negotiated.
+    type OutboundOpenInfo: Send + 'static;
+
+    /// The upgrade to set up for the listening side of a connection.
+    fn listen_protocol(&self) -> SubstreamProtocol<Self::InboundProtocol, Self::InboundOpenInfo>;
+
+    /// Injects an event coming from the [`NetworkBehaviour`](crate::NetworkBehaviour).
+    fn on_behaviour_event(&mut self, event: Self::InEvent);
+
+    /// Returns until when the connection should be kept alive.
+    fn connection_keep_alive(&self) -> KeepAlive;
+
+    /// Polls the handler for events it would like to produce.
+    fn poll(
+        &mut self,
+        cx: &mut Context<'_>,
+    ) -> Poll<ConnectionHandlerEvent<Self::OutboundProtocol, Self::OutboundOpenInfo, Self::OutEvent>>;
+
+    /// Injects an event coming from the connection, such as a successfully negotiated substream
+    /// or a failed upgrade.
+    fn on_connection_event(
+        &mut self,
+        event: ConnectionEvent<
+            Self::InboundProtocol,
+            Self::OutboundProtocol,
+            Self::InboundOpenInfo,
+            Self::OutboundOpenInfo,
+        >,
+    );
+
+    /// Polls the handler for a graceful shutdown, after [`ConnectionHandler::connection_keep_alive`]
+    /// has signalled that the connection is no longer needed.
+    ///
+    /// The [`Swarm`](crate::Swarm) calls this repeatedly, forwarding every `Some(event)` to the
+    /// behaviour, until it returns `Poll::Ready(None)`; only then does it tear down the
+    /// connection's substream multiplexer. Once this method has returned `Poll::Ready(None)`,
+    /// [`ConnectionHandler::poll`] must not be called again.
+    ///
+    /// The default implementation has no in-flight work to drain and closes immediately.
+    fn poll_close(&mut self, _cx: &mut Context<'_>) -> Poll<Option<Self::OutEvent>> {
+        Poll::Ready(None)
+    }
+
+    /// Adapts this handler to take in a different in-event, by applying a closure that borrows
+    /// a [`Self::InEvent`] out of it.
+    ///
+    /// Events for which the closure returns `None` are dropped rather than forwarded. This lets
+    /// a parent behaviour embed a child handler whose event types differ from its own, without
+    /// hand-writing a wrapper.
+    fn map_in_event<TNewIn, TMap>(self, f: TMap) -> MapInEvent<Self, TNewIn, TMap>
+    where
+        Self: Sized,
+        Self::InEvent: Clone,
+        TMap: Fn(&TNewIn) -> Option<&Self::InEvent>,
+    {
+        MapInEvent::new(self, f)
+    }
+
+    /// Adapts this handler to produce a different out-event, by applying a closure to every
+    /// [`ConnectionHandlerEvent::Custom`] it emits.
+    ///
+    /// Upgrade and keep-alive events pass through untouched.
+    fn map_out_event<TMap, TNewOut>(self, f: TMap) -> MapOutEvent<Self, TMap>
+    where
+        Self: Sized,
+        TMap: FnMut(Self::OutEvent) -> TNewOut,
+    {
+        MapOutEvent::new(self, f)
+    }
+}
@@ -0,0 +1,79 @@
+use crate::handler::{ConnectionEvent, ConnectionHandler, ConnectionHandlerEvent, KeepAlive};
+use std::marker::PhantomData;
+use std::task::{Context, Poll};
+
+/// Wraps around an implementation of [`ConnectionHandler`] and maps the in-event that it
+/// receives from the [`NetworkBehaviour`](crate::NetworkBehaviour).
+///
+/// Produced by [`ConnectionHandler::map_in_event`].
+pub struct MapInEvent<TConnectionHandler, TNewIn, TMap> {
+    inner: TConnectionHandler,
+    map: TMap,
+    marker: PhantomData<TNewIn>,
+}
+
+impl<TConnectionHandler, TNewIn, TMap> MapInEvent<TConnectionHandler, TNewIn, TMap> {
+    pub(crate) fn new(inner: TConnectionHandler, map: TMap) -> Self {
+        Self {
+            inner,
+            map,
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<TConnectionHandler, TNewIn, TMap> ConnectionHandler
+    for MapInEvent<TConnectionHandler, TNewIn, TMap>
+where
+    TConnectionHandler: ConnectionHandler,
+    TConnectionHandler::InEvent: Clone,
+    TNewIn: Send + 'static,
+    TMap: Fn(&TNewIn) -> Option<&TConnectionHandler::InEvent> + Send + 'static,
+{
+    type InEvent = TNewIn;
+    type OutEvent = TConnectionHandler::OutEvent;
+    type InboundProtocol = TConnectionHandler::InboundProtocol;
+    type OutboundProtocol = TConnectionHandler::OutboundProtocol;
+    type InboundOpenInfo = TConnectionHandler::InboundOpenInfo;
+    type OutboundOpenInfo = TConnectionHandler::OutboundOpenInfo;
+
+    fn listen_protocol(
+        &self,
+    ) -> crate::handler::SubstreamProtocol<Self::InboundProtocol, Self::InboundOpenInfo> {
+        self.inner.listen_protocol()
+    }
+
+    fn on_behaviour_event(&mut self, event: Self::InEvent) {
+        if let Some(event) = (self.map)(&event) {
+            self.inner.on_behaviour_event(event.clone());
+        }
+    }
+
+    fn connection_keep_alive(&self) -> KeepAlive {
+        self.inner.connection_keep_alive()
+    }
+
+    fn poll(
+        &mut self,
+        cx: &mut Context<'_>,
+    ) -> Poll<ConnectionHandlerEvent<Self::OutboundProtocol, Self::OutboundOpenInfo, Self::OutEvent>>
+    {
+        self.inner.poll(cx)
+    }
+
+    fn on_connection_event(
+        &mut self,
+        event: ConnectionEvent<
+            Self::InboundProtocol,
+            Self::OutboundProtocol,
+            Self::InboundOpenInfo,
+            Self::OutboundOpenInfo,
+        >,
+    ) {
+        self.inner.on_connection_event(event)
+    }
+
+    fn poll_close(&mut self, cx: &mut Context<'_>) -> Poll<Option<Self::OutEvent>> {
+        self.inner.poll_close(cx)
+    }
+}
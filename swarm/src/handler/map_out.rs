@@ -0,0 +1,79 @@
+use crate::handler::{ConnectionEvent, ConnectionHandler, ConnectionHandlerEvent, KeepAlive};
+use std::task::{Context, Poll};
+
+/// Wraps around an implementation of [`ConnectionHandler`] and maps the out-event that it
+/// produces, so it can be reported to a [`NetworkBehaviour`](crate::NetworkBehaviour) expecting
+/// a different event type.
+///
+/// Produced by [`ConnectionHandler::map_out_event`].
+pub struct MapOutEvent<TConnectionHandler, TMap> {
+    inner: TConnectionHandler,
+    map: TMap,
+}
+
+impl<TConnectionHandler, TMap> MapOutEvent<TConnectionHandler, TMap> {
+    pub(crate) fn new(inner: TConnectionHandler, map: TMap) -> Self {
+        Self { inner, map }
+    }
+}
+
+impl<TConnectionHandler, TMap, TNewOut> ConnectionHandler for MapOutEvent<TConnectionHandler, TMap>
+where
+    TConnectionHandler: ConnectionHandler,
+    TMap: FnMut(TConnectionHandler::OutEvent) -> TNewOut + Send + 'static,
+    TNewOut: Send + 'static,
+{
+    type InEvent = TConnectionHandler::InEvent;
+    type OutEvent = TNewOut;
+    type InboundProtocol = TConnectionHandler::InboundProtocol;
+    type OutboundProtocol = TConnectionHandler::OutboundProtocol;
+    type InboundOpenInfo = TConnectionHandler::InboundOpenInfo;
+    type OutboundOpenInfo = TConnectionHandler::OutboundOpenInfo;
+
+    fn listen_protocol(
+        &self,
+    ) -> crate::handler::SubstreamProtocol<Self::InboundProtocol, Self::InboundOpenInfo> {
+        self.inner.listen_protocol()
+    }
+
+    fn on_behaviour_event(&mut self, event: Self::InEvent) {
+        self.inner.on_behaviour_event(event)
+    }
+
+    fn connection_keep_alive(&self) -> KeepAlive {
+        self.inner.connection_keep_alive()
+    }
+
+    fn poll(
+        &mut self,
+        cx: &mut Context<'_>,
+    ) -> Poll<ConnectionHandlerEvent<Self::OutboundProtocol, Self::OutboundOpenInfo, Self::OutEvent>>
+    {
+        self.inner.poll(cx).map(|event| match event {
+            ConnectionHandlerEvent::OutboundSubstreamRequest { protocol } => {
+                ConnectionHandlerEvent::OutboundSubstreamRequest { protocol }
+            }
+            ConnectionHandlerEvent::Custom(event) => {
+                ConnectionHandlerEvent::Custom((self.map)(event))
+            }
+        })
+    }
+
+    fn on_connection_event(
+        &mut self,
+        event: ConnectionEvent<
+            Self::InboundProtocol,
+            Self::OutboundProtocol,
+            Self::InboundOpenInfo,
+            Self::OutboundOpenInfo,
+        >,
+    ) {
+        self.inner.on_connection_event(event)
+    }
+
+    fn poll_close(&mut self, cx: &mut Context<'_>) -> Poll<Option<Self::OutEvent>> {
+        self.inner
+            .poll_close(cx)
+            .map(|maybe_event| maybe_event.map(&mut self.map))
+    }
+}
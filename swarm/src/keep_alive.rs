@@ -1,12 +1,15 @@
-use crate::behaviour::{FromSwarm, NetworkBehaviour, NetworkBehaviourAction, PollParameters};
+use crate::behaviour::{
+    ConnectionDenied, FromSwarm, NetworkBehaviour, NetworkBehaviourAction, PollParameters,
+};
 use crate::handler::{
     ConnectionEvent, ConnectionHandlerEvent, FullyNegotiatedInbound, FullyNegotiatedOutbound,
     KeepAlive, SubstreamProtocol,
 };
 use libp2p_core::connection::ConnectionId;
 use libp2p_core::upgrade::DeniedUpgrade;
-use libp2p_core::PeerId;
+use libp2p_core::{Endpoint, Multiaddr, PeerId};
 use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
 use void::Void;
 
 /// Implementation of [`NetworkBehaviour`] that doesn't do anything other than keep all connections alive.
@@ -15,15 +18,74 @@ use void::Void;
 /// The caveat is that open connections consume system resources and should thus be shutdown when
 /// they are not in use. Connections can also fail at any time so really, your application should be
 /// designed to establish them when necessary, making the use of this behaviour likely redundant.
+///
+/// By default, connections are kept alive indefinitely. Use [`Behaviour::with_idle_timeout`] to
+/// instead close connections once they have been idle, i.e. seen no activity, for the given
+/// [`Duration`].
 #[derive(Default)]
-pub struct Behaviour;
+pub struct Behaviour {
+    idle_timeout: Option<Duration>,
+}
+
+impl Behaviour {
+    /// Keep connections alive only while they have seen activity within the last `timeout`.
+    ///
+    /// Once a connection has been idle for longer than `timeout`, the [`Swarm`](crate::Swarm)
+    /// is free to close it.
+    pub fn with_idle_timeout(timeout: Duration) -> Self {
+        Self {
+            idle_timeout: Some(timeout),
+        }
+    }
+}
 
 impl NetworkBehaviour for Behaviour {
     type ConnectionHandler = ConnectionHandler;
     type OutEvent = Void;
 
-    fn new_handler(&mut self) -> Self::ConnectionHandler {
-        ConnectionHandler
+    fn handle_pending_inbound_connection(
+        &mut self,
+        _connection_id: ConnectionId,
+        _local_addr: &Multiaddr,
+        _remote_addr: &Multiaddr,
+    ) -> Result<(), ConnectionDenied> {
+        Ok(())
+    }
+
+    fn handle_established_inbound_connection(
+        &mut self,
+        _connection_id: ConnectionId,
+        _peer: PeerId,
+        _local_addr: &Multiaddr,
+        _remote_addr: &Multiaddr,
+    ) -> Result<Self::ConnectionHandler, ConnectionDenied> {
+        Ok(ConnectionHandler {
+            idle_timeout: self.idle_timeout,
+            last_activity: Instant::now(),
+        })
+    }
+
+    fn handle_pending_outbound_connection(
+        &mut self,
+        _connection_id: ConnectionId,
+        _maybe_peer: Option<PeerId>,
+        _addresses: &[Multiaddr],
+        _effective_role: Endpoint,
+    ) -> Result<Vec<Multiaddr>, ConnectionDenied> {
+        Ok(Vec::new())
+    }
+
+    fn handle_established_outbound_connection(
+        &mut self,
+        _connection_id: ConnectionId,
+        _peer: PeerId,
+        _addr: &Multiaddr,
+        _role_override: Endpoint,
+    ) -> Result<Self::ConnectionHandler, ConnectionDenied> {
+        Ok(ConnectionHandler {
+            idle_timeout: self.idle_timeout,
+            last_activity: Instant::now(),
+        })
     }
 
     fn on_connection_handler_event(&mut self, _: PeerId, _: ConnectionId, event: Void) {
@@ -57,8 +119,27 @@ impl NetworkBehaviour for Behaviour {
 }
 
 /// Implementation of [`ConnectionHandler`] that doesn't handle anything but keeps the connection alive.
+///
+/// This handler never has any in-flight work to drain once the connection is no longer kept
+/// alive, so it relies on the trait's default `poll_close` implementation to report closure
+/// immediately.
+///
+/// Because its `InEvent` and `OutEvent` are both [`Void`], this handler can be embedded inside a
+/// larger composite handler as-is via the trait's `map_in_event`/`map_out_event` combinators,
+/// without writing a dedicated wrapper.
 #[derive(Clone, Debug)]
-pub struct ConnectionHandler;
+pub struct ConnectionHandler {
+    /// The configured idle timeout, if any. `None` means the connection is kept alive forever.
+    idle_timeout: Option<Duration>,
+    /// The instant at which this handler last observed activity on the connection.
+    last_activity: Instant,
+}
+
+impl ConnectionHandler {
+    fn record_activity(&mut self) {
+        self.last_activity = Instant::now();
+    }
+}
 
 impl crate::handler::ConnectionHandler for ConnectionHandler {
     type InEvent = Void;
@@ -77,7 +158,10 @@ impl crate::handler::ConnectionHandler for ConnectionHandler {
     }
 
     fn connection_keep_alive(&self) -> KeepAlive {
-        KeepAlive::Yes
+        match self.idle_timeout {
+            Some(timeout) => KeepAlive::Until(self.last_activity + timeout),
+            None => KeepAlive::Yes,
+        }
     }
 
     fn poll(
@@ -97,6 +181,8 @@ impl crate::handler::ConnectionHandler for ConnectionHandler {
             Self::OutboundOpenInfo,
         >,
     ) {
+        self.record_activity();
+
         match event {
             ConnectionEvent::FullyNegotiatedInbound(FullyNegotiatedInbound {
                 protocol, ..
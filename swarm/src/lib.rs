@@ -0,0 +1,11 @@
+mod behaviour;
+mod handler;
+pub mod keep_alive;
+
+pub use behaviour::{
+    ConnectionDenied, FromSwarm, NetworkBehaviour, NetworkBehaviourAction, PollParameters,
+};
+pub use handler::{
+    ConnectionEvent, ConnectionHandler, ConnectionHandlerEvent, FullyNegotiatedInbound,
+    FullyNegotiatedOutbound, KeepAlive, MapInEvent, MapOutEvent, SubstreamProtocol,
+};